@@ -0,0 +1,123 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+fn default_host() -> String {
+    "127.0.0.1".into()
+}
+
+fn default_port() -> u16 {
+    0 // 0 = allocate a free ephemeral port at startup
+}
+
+fn default_extra_args() -> Vec<String> {
+    Vec::new()
+}
+
+fn default_python_interpreter() -> String {
+    "python".into()
+}
+
+fn default_startup_timeout_secs() -> u64 {
+    20
+}
+
+fn default_dev_auto_spawn() -> bool {
+    false
+}
+
+fn default_backend_version() -> String {
+    // Baked in by the sidecar packaging step (not the Tauri app's own crate
+    // version) via the `FLASK_BACKEND_VERSION` build-time env var. Falls
+    // back to an unconditionally-outdated version so an install that's
+    // missing it still compares sanely against the update manifest.
+    option_env!("FLASK_BACKEND_VERSION").unwrap_or("0.0.0").to_string()
+}
+
+const CONFIG_FILE_NAME: &str = "backend.json";
+
+/// Settings controlling how the `flask-backend` sidecar (or, in debug
+/// builds, the Python interpreter directly) is launched. Loaded from
+/// `backend.json` in the app config dir; any field missing from the file
+/// falls back to its default.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackendConfig {
+    #[serde(default = "default_host")]
+    pub host: String,
+    #[serde(default = "default_port")]
+    pub port: u16,
+    #[serde(default = "default_extra_args")]
+    pub extra_args: Vec<String>,
+    #[serde(default = "default_python_interpreter")]
+    pub python_interpreter: String,
+    #[serde(default = "default_startup_timeout_secs")]
+    pub startup_timeout_secs: u64,
+    /// In debug builds, auto-spawn `python_interpreter` against `main.py`
+    /// instead of requiring it to be started manually. Can also be enabled
+    /// by setting the `SHORTLIST_DEV_AUTO_SPAWN` env var. Ignored in release
+    /// builds, which always launch the bundled sidecar.
+    #[serde(default = "default_dev_auto_spawn")]
+    pub dev_auto_spawn: bool,
+    /// Version of the bundled `flask-backend` sidecar, compared against the
+    /// manifest fetched from `update_manifest_url` to decide whether a
+    /// newer build should be downloaded.
+    #[serde(default = "default_backend_version")]
+    pub backend_version: String,
+    /// URL of a JSON manifest (`{"version", "url", "signature"}`) describing
+    /// the latest `flask-backend` build. Self-updating is disabled unless
+    /// this and `update_public_key` are both set.
+    #[serde(default)]
+    pub update_manifest_url: Option<String>,
+    /// minisign public key (base64) used to verify a downloaded sidecar
+    /// binary before it's swapped in.
+    #[serde(default)]
+    pub update_public_key: Option<String>,
+}
+
+impl Default for BackendConfig {
+    fn default() -> Self {
+        Self {
+            host: default_host(),
+            port: default_port(),
+            extra_args: default_extra_args(),
+            python_interpreter: default_python_interpreter(),
+            startup_timeout_secs: default_startup_timeout_secs(),
+            dev_auto_spawn: default_dev_auto_spawn(),
+            backend_version: default_backend_version(),
+            update_manifest_url: None,
+            update_public_key: None,
+        }
+    }
+}
+
+/// Loads `backend.json` from `config_dir`, creating it with defaults if it
+/// doesn't exist. Fields present in the file override their defaults;
+/// fields absent from the file (or the whole file) fall back to them.
+pub fn load_or_init(config_dir: &Path) -> BackendConfig {
+    let path = config_dir.join(CONFIG_FILE_NAME);
+
+    if let Ok(contents) = std::fs::read_to_string(&path) {
+        return match serde_json::from_str(&contents) {
+            Ok(config) => config,
+            Err(err) => {
+                eprintln!("[tauri] failed to parse {path:?}, using defaults: {err}");
+                BackendConfig::default()
+            }
+        };
+    }
+
+    let config = BackendConfig::default();
+    if let Err(err) = std::fs::create_dir_all(config_dir) {
+        eprintln!("[tauri] failed to create config dir {config_dir:?}: {err}");
+        return config;
+    }
+    match serde_json::to_string_pretty(&config) {
+        Ok(json) => {
+            if let Err(err) = std::fs::write(&path, json) {
+                eprintln!("[tauri] failed to write default config to {path:?}: {err}");
+            }
+        }
+        Err(err) => eprintln!("[tauri] failed to serialize default config: {err}"),
+    }
+    config
+}
@@ -1,38 +1,91 @@
-use tauri::Manager;
+mod config;
+mod sidecar;
+mod updater;
+
+use std::sync::{Arc, Mutex};
+
+use sidecar::{BackendPort, Launch, LaunchState, ShutdownFlag, SidecarState};
+use tauri::{Emitter, Manager, WindowEvent};
+
+#[tauri::command]
+fn backend_port(state: tauri::State<BackendPort>) -> u16 {
+    state.0
+}
 
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
+        .manage(SidecarState::default())
+        .manage(ShutdownFlag::default())
+        .invoke_handler(tauri::generate_handler![backend_port])
         .setup(|app| {
-            if cfg!(debug_assertions) {
-                eprintln!("[tauri] Debug mode — start Flask manually: uv run python main.py");
+            let app_data_dir = app
+                .path()
+                .app_data_dir()
+                .expect("failed to resolve appDataDir");
+            std::fs::create_dir_all(&app_data_dir).expect("failed to create appDataDir");
+
+            let app_config_dir = app
+                .path()
+                .app_config_dir()
+                .expect("failed to resolve appConfigDir");
+            let backend_config = config::load_or_init(&app_config_dir);
+
+            // Resolved and managed unconditionally, before branching on how
+            // (or whether) the backend gets auto-spawned below, so the
+            // frontend's `backend_port` invoke has something to read even
+            // in the "start Flask manually" dev workflow.
+            let port = if backend_config.port != 0 {
+                backend_config.port
             } else {
-                let app_data_dir = app
-                    .path()
-                    .app_data_dir()
-                    .expect("failed to resolve appDataDir");
-
-                std::fs::create_dir_all(&app_data_dir)
-                    .expect("failed to create appDataDir");
-
-                let sidecar = app
-                    .shell()
-                    .sidecar("flask-backend")
-                    .expect("failed to create sidecar command")
-                    .args([
-                        "--data-dir",
-                        app_data_dir.to_str().unwrap(),
-                        "--port",
-                        "5000",
-                    ]);
-
-                let (mut _rx, _child) = sidecar.spawn().expect("failed to spawn sidecar");
-
-                eprintln!("[tauri] Flask sidecar started with data-dir: {:?}", app_data_dir);
-            }
+                sidecar::allocate_port()
+            };
+            app.manage(BackendPort(port));
+            app.emit("backend-port", port)?;
+
+            let dev_auto_spawn = std::env::var("SHORTLIST_DEV_AUTO_SPAWN").is_ok()
+                || backend_config.dev_auto_spawn;
+
+            let launch = if cfg!(debug_assertions) {
+                if dev_auto_spawn {
+                    Launch::Python {
+                        interpreter: backend_config.python_interpreter.clone(),
+                        script: std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                            .join("..")
+                            .join("main.py"),
+                    }
+                } else {
+                    eprintln!(
+                        "[tauri] Debug mode — start Flask manually: uv run python main.py \
+                         (or set dev_auto_spawn/SHORTLIST_DEV_AUTO_SPAWN to have Tauri do it)"
+                    );
+                    return Ok(());
+                }
+            } else {
+                Launch::Sidecar
+            };
+
+            let launch_state = Arc::new(Mutex::new(LaunchState {
+                launch,
+                version: backend_config.backend_version.clone(),
+            }));
+
+            sidecar::spawn_supervised(
+                app.handle().clone(),
+                app_data_dir,
+                backend_config,
+                port,
+                launch_state,
+            );
 
             Ok(())
         })
+        .on_window_event(|window, event| {
+            if let WindowEvent::CloseRequested { .. } = event {
+                window.state::<ShutdownFlag>().request();
+                window.state::<SidecarState>().kill();
+            }
+        })
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
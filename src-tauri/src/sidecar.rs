@@ -0,0 +1,237 @@
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_shell::process::{CommandChild, CommandEvent};
+use tauri_plugin_shell::ShellExt;
+
+use crate::config::BackendConfig;
+use crate::updater;
+
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+const HEALTHY_RESET_AFTER: Duration = Duration::from_secs(60);
+const READY_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Tauri-managed state holding the currently supervised `flask-backend` child,
+/// so it can be killed (e.g. on window close) or swapped out on restart.
+#[derive(Default)]
+pub struct SidecarState(pub Mutex<Option<CommandChild>>);
+
+impl SidecarState {
+    pub fn kill(&self) {
+        if let Some(child) = self.0.lock().unwrap().take() {
+            let _ = child.kill();
+        }
+    }
+}
+
+/// Tauri-managed state tripped by app shutdown so the supervisor loop knows
+/// a `Terminated` event means "the app is closing", not "the backend
+/// crashed" — without this it restarts the backend in the narrow window
+/// between `CloseRequested` killing the child and the app process exiting,
+/// which is the same "Flask outlives the app" problem this module exists
+/// to prevent, just moved to a smaller window.
+#[derive(Default)]
+pub struct ShutdownFlag(AtomicBool);
+
+impl ShutdownFlag {
+    pub fn request(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_requested(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// Tauri-managed state holding the port the sidecar was launched with, so
+/// the frontend can ask for it via the `backend_port` command.
+pub struct BackendPort(pub u16);
+
+/// Binds an ephemeral local port and immediately releases it so the sidecar
+/// can be started against a port that's known to be free.
+pub fn allocate_port() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("failed to bind ephemeral port");
+    listener.local_addr().expect("failed to read local_addr").port()
+}
+
+/// How the backend process is launched: the bundled `flask-backend` sidecar
+/// in release builds, the configured Python interpreter against `main.py`
+/// when dev auto-spawn is enabled, or a standalone binary swapped in by the
+/// updater.
+#[derive(Clone)]
+pub enum Launch {
+    Sidecar,
+    Python { interpreter: String, script: std::path::PathBuf },
+    Binary(std::path::PathBuf),
+}
+
+/// The currently active `Launch`, paired with the version of the backend it
+/// launches. Shared behind a mutex so the updater can swap both in lockstep
+/// when it installs a new binary — the supervisor re-reads this on every
+/// (re)start, and the update check compares against the live `version`
+/// rather than whatever was loaded from `backend.json` at startup (which
+/// would otherwise look stale forever and re-trigger the update on every
+/// restart, including the one the update itself causes).
+pub struct LaunchState {
+    pub launch: Launch,
+    pub version: String,
+}
+
+pub type SharedLaunch = Arc<Mutex<LaunchState>>;
+
+/// Spawns the backend process and keeps it alive: stdout/stderr lines are
+/// forwarded to the app log and to the frontend, and an unexpected
+/// `Terminated` event triggers a restart with exponential backoff — unless
+/// `ShutdownFlag` has been tripped, in which case the supervisor exits
+/// instead of racing the app's own shutdown. `launch` is re-read on every
+/// (re)start so the updater can swap in a new binary by mutating it and
+/// killing the current child.
+pub fn spawn_supervised(
+    app: AppHandle,
+    data_dir: std::path::PathBuf,
+    config: BackendConfig,
+    port: u16,
+    launch: SharedLaunch,
+) {
+    tauri::async_runtime::spawn(async move {
+        let mut backoff = INITIAL_BACKOFF;
+        let ready_timeout = Duration::from_secs(config.startup_timeout_secs);
+
+        loop {
+            if app.state::<ShutdownFlag>().is_requested() {
+                break;
+            }
+
+            let mut args = vec![
+                "--data-dir".to_string(),
+                data_dir.to_str().unwrap().to_string(),
+                "--host".to_string(),
+                config.host.clone(),
+                "--port".to_string(),
+                port.to_string(),
+            ];
+            args.extend(config.extra_args.iter().cloned());
+
+            let current_launch = launch.lock().unwrap().launch.clone();
+            let command = match current_launch {
+                Launch::Sidecar => app
+                    .shell()
+                    .sidecar("flask-backend")
+                    .map(|cmd| cmd.args(args)),
+                Launch::Python { interpreter, script } => {
+                    let mut full_args = vec![script.to_str().unwrap().to_string()];
+                    full_args.extend(args.drain(..));
+                    Ok(app.shell().command(interpreter).args(full_args))
+                }
+                Launch::Binary(path) => {
+                    Ok(app.shell().command(path.to_str().unwrap()).args(args.drain(..)))
+                }
+            };
+
+            let command = match command {
+                Ok(cmd) => cmd,
+                Err(err) => {
+                    eprintln!("[tauri] failed to create backend command: {err}");
+                    break;
+                }
+            };
+
+            let (mut rx, child) = match command.spawn() {
+                Ok(pair) => pair,
+                Err(err) => {
+                    eprintln!("[tauri] failed to spawn sidecar: {err}");
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(MAX_BACKOFF);
+                    continue;
+                }
+            };
+
+            *app.state::<SidecarState>().0.lock().unwrap() = Some(child);
+            let started_at = Instant::now();
+            eprintln!("[tauri] backend started on port {port} with data-dir: {:?}", data_dir);
+
+            {
+                let app = app.clone();
+                let config = config.clone();
+                let data_dir = data_dir.clone();
+                let launch = launch.clone();
+                tauri::async_runtime::spawn(async move {
+                    if wait_until_ready(app.clone(), port, ready_timeout).await {
+                        updater::check_and_apply(app, config, data_dir, launch).await;
+                    }
+                });
+            }
+
+            loop {
+                let Some(event) = rx.recv().await else {
+                    break;
+                };
+
+                match event {
+                    CommandEvent::Stdout(line) => {
+                        let line = String::from_utf8_lossy(&line).to_string();
+                        eprintln!("[flask] {line}");
+                        let _ = app.emit("sidecar-log", &line);
+                    }
+                    CommandEvent::Stderr(line) => {
+                        let line = String::from_utf8_lossy(&line).to_string();
+                        eprintln!("[flask:err] {line}");
+                        let _ = app.emit("sidecar-log", &line);
+                    }
+                    CommandEvent::Terminated(payload) => {
+                        eprintln!("[tauri] backend process terminated: {payload:?}");
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+
+            app.state::<SidecarState>().0.lock().unwrap().take();
+
+            if app.state::<ShutdownFlag>().is_requested() {
+                break;
+            }
+
+            if started_at.elapsed() >= HEALTHY_RESET_AFTER {
+                backoff = INITIAL_BACKOFF;
+            }
+
+            eprintln!("[tauri] restarting backend process in {backoff:?}");
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
+    });
+}
+
+/// Polls the sidecar's `/health` endpoint until it returns a successful
+/// HTTP response or `timeout` elapses. On a successful response it emits
+/// `backend-ready` so the frontend can drop its loading state; on timeout
+/// nothing is emitted. A bare TCP connect isn't enough:
+/// the OS can accept the handshake before Flask has finished setting up
+/// routes/DB connections, so this requires an actual response. Returns
+/// whether the backend became ready in time.
+async fn wait_until_ready(app: AppHandle, port: u16, timeout: Duration) -> bool {
+    let deadline = Instant::now() + timeout;
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(2))
+        .build()
+        .unwrap_or_else(|_| reqwest::Client::new());
+    let url = format!("http://127.0.0.1:{port}/health");
+
+    while Instant::now() < deadline {
+        if let Ok(resp) = client.get(&url).send().await {
+            if resp.status().is_success() {
+                let _ = app.emit("backend-ready", port);
+                return true;
+            }
+        }
+        tokio::time::sleep(READY_POLL_INTERVAL).await;
+    }
+
+    eprintln!("[tauri] backend did not become ready on port {port} within {timeout:?}");
+    false
+}
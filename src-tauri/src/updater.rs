@@ -0,0 +1,100 @@
+use std::path::PathBuf;
+
+use minisign_verify::{PublicKey, Signature};
+use serde::Deserialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::config::BackendConfig;
+use crate::sidecar::{Launch, SharedLaunch, SidecarState};
+
+#[derive(Debug, Deserialize)]
+struct UpdateManifest {
+    version: String,
+    url: String,
+    signature: String,
+}
+
+const BACKEND_BIN_NAME: &str = if cfg!(windows) {
+    "flask-backend.exe"
+} else {
+    "flask-backend"
+};
+
+/// Checks `config.update_manifest_url` for a newer `flask-backend` build,
+/// and if one is found, downloads it, verifies its minisign signature
+/// against `config.update_public_key`, and swaps it into `launch` before
+/// killing the current child so the supervisor restarts onto it. No-op if
+/// either config value is unset. The version comparison reads the *live*
+/// version out of `launch`, not `config.backend_version` — the latter is
+/// only ever the value read from `backend.json` at startup, so using it
+/// here would make every restart after an update look stale again and
+/// loop on re-downloading/re-applying the same build forever.
+pub async fn check_and_apply(
+    app: AppHandle,
+    config: BackendConfig,
+    data_dir: PathBuf,
+    launch: SharedLaunch,
+) {
+    let (Some(manifest_url), Some(public_key)) =
+        (config.update_manifest_url.clone(), config.update_public_key.clone())
+    else {
+        return;
+    };
+
+    if let Err(err) = run(&app, &manifest_url, &public_key, &data_dir, &launch).await {
+        eprintln!("[tauri] backend self-update check failed: {err}");
+        let _ = app.emit("backend-update-error", err.to_string());
+    }
+}
+
+async fn run(
+    app: &AppHandle,
+    manifest_url: &str,
+    public_key: &str,
+    data_dir: &PathBuf,
+    launch: &SharedLaunch,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let _ = app.emit("backend-update-progress", "checking");
+    let manifest: UpdateManifest = reqwest::get(manifest_url).await?.json().await?;
+
+    let current_version = launch.lock().unwrap().version.clone();
+    if !is_newer(&manifest.version, &current_version) {
+        let _ = app.emit("backend-update-progress", "up-to-date");
+        return Ok(());
+    }
+
+    let _ = app.emit("backend-update-progress", "downloading");
+    let bytes = reqwest::get(&manifest.url).await?.bytes().await?;
+
+    let _ = app.emit("backend-update-progress", "verifying");
+    let key = PublicKey::from_base64(public_key)?;
+    let signature = Signature::decode(&manifest.signature)?;
+    key.verify(&bytes, &signature, false)?;
+
+    let bin_dir = data_dir.join("bin");
+    std::fs::create_dir_all(&bin_dir)?;
+    let bin_path = bin_dir.join(format!("{BACKEND_BIN_NAME}-{}", manifest.version));
+    std::fs::write(&bin_path, &bytes)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&bin_path, std::fs::Permissions::from_mode(0o755))?;
+    }
+
+    {
+        let mut state = launch.lock().unwrap();
+        state.launch = Launch::Binary(bin_path);
+        state.version = manifest.version;
+    }
+    let _ = app.emit("backend-update-progress", "restarting");
+    app.state::<SidecarState>().kill();
+
+    Ok(())
+}
+
+fn is_newer(candidate: &str, current: &str) -> bool {
+    match (semver::Version::parse(candidate), semver::Version::parse(current)) {
+        (Ok(candidate), Ok(current)) => candidate > current,
+        _ => candidate != current,
+    }
+}